@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use aws_sdk_cloudwatch::model::{ComparisonOperator, Dimension, Statistic};
+use aws_sdk_cloudwatch::Client;
+use serde::Deserialize;
+
+use crate::error::AppError;
+
+/// A single alarm to provision, as described in a `--spec-file`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct AlarmSpec {
+    pub name: String,
+    pub namespace: String,
+    pub metric_name: String,
+    #[serde(default)]
+    pub dimensions: HashMap<String, String>,
+    pub statistic: String,
+    pub period: i32,
+    pub threshold: f64,
+    pub comparison_operator: String,
+    pub evaluation_periods: i32,
+    #[serde(default)]
+    pub alarm_actions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AlarmSpecFile {
+    alarm: Vec<AlarmSpec>,
+}
+
+/// Loads alarm specs from a TOML or JSON file, dispatching on extension.
+pub fn load_alarm_specs(path: &str) -> Result<Vec<AlarmSpec>, AppError> {
+    let contents = std::fs::read_to_string(path)?;
+    if path.ends_with(".json") {
+        serde_json::from_str(&contents)
+            .map_err(|e| AppError::Config(format!("unable to parse {} as JSON: {}", path, e)))
+    } else {
+        let parsed: AlarmSpecFile = toml::from_str(&contents)
+            .map_err(|e| AppError::Config(format!("unable to parse {} as TOML: {}", path, e)))?;
+        Ok(parsed.alarm)
+    }
+}
+
+/// Validates `statistic` against the `Statistic` enum, rejecting anything
+/// that would otherwise silently become `Statistic::Unknown` on the wire.
+fn parse_statistic(statistic: &str) -> Result<Statistic, AppError> {
+    match Statistic::from(statistic) {
+        Statistic::Unknown(_) => Err(AppError::Config(format!(
+            "unknown statistic '{}' (expected one of SampleCount, Average, Sum, Minimum, Maximum)",
+            statistic
+        ))),
+        known => Ok(known),
+    }
+}
+
+/// Validates `op` against the `ComparisonOperator` enum, rejecting a
+/// typo'd value rather than sending `ComparisonOperator::Unknown`.
+fn parse_comparison_operator(op: &str) -> Result<ComparisonOperator, AppError> {
+    match ComparisonOperator::from(op) {
+        ComparisonOperator::Unknown(_) => Err(AppError::Config(format!(
+            "unknown comparison operator '{}' (expected one of GreaterThanOrEqualToThreshold, GreaterThanThreshold, LessThanThreshold, LessThanOrEqualToThreshold, LessThanLowerOrGreaterThanUpperThreshold, LessThanLowerThreshold, GreaterThanUpperThreshold)",
+            op
+        ))),
+        known => Ok(known),
+    }
+}
+
+/// Provisions every spec in `specs` via `PutMetricAlarm`.
+pub async fn put_alarms(client: &Client, specs: &[AlarmSpec]) -> Result<(), AppError> {
+    for spec in specs {
+        let dimensions: Vec<Dimension> = spec
+            .dimensions
+            .iter()
+            .map(|(name, value)| Dimension::builder().name(name).value(value).build())
+            .collect();
+
+        client
+            .put_metric_alarm()
+            .alarm_name(&spec.name)
+            .namespace(&spec.namespace)
+            .metric_name(&spec.metric_name)
+            .set_dimensions(Some(dimensions))
+            .statistic(parse_statistic(&spec.statistic)?)
+            .period(spec.period)
+            .threshold(spec.threshold)
+            .comparison_operator(parse_comparison_operator(&spec.comparison_operator)?)
+            .evaluation_periods(spec.evaluation_periods)
+            .set_alarm_actions(Some(spec.alarm_actions.clone()))
+            .send()
+            .await
+            .map_err(aws_sdk_cloudwatch::Error::from)?;
+        println!("put alarm: {}", spec.name);
+    }
+    Ok(())
+}
+
+/// Prints every alarm currently defined in the account, along with its
+/// `StateValue` (`OK`, `ALARM`, `INSUFFICIENT_DATA`), paginating through
+/// `NextToken` so accounts with many alarms aren't truncated.
+pub async fn list_alarms(client: &Client) -> Result<(), AppError> {
+    let mut next_token = None;
+
+    loop {
+        let resp = client
+            .describe_alarms()
+            .set_next_token(next_token.clone())
+            .send()
+            .await
+            .map_err(aws_sdk_cloudwatch::Error::from)?;
+
+        for alarm in resp.metric_alarms().unwrap_or_default() {
+            println!(
+                "{:<40} {:<20} {}",
+                alarm.alarm_name().unwrap_or_default(),
+                alarm
+                    .state_value()
+                    .map(|s| s.as_str())
+                    .unwrap_or_default(),
+                alarm.metric_name().unwrap_or_default(),
+            );
+        }
+
+        next_token = resp.next_token().map(String::from);
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Deletes every alarm in `names` with a single `DeleteAlarms` call.
+pub async fn delete_alarms(client: &Client, names: &[String]) -> Result<(), AppError> {
+    client
+        .delete_alarms()
+        .set_alarm_names(Some(names.to_vec()))
+        .send()
+        .await
+        .map_err(aws_sdk_cloudwatch::Error::from)?;
+    println!("deleted alarms: {:?}", names);
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use aws_credential_types::Credentials;
+
+use crate::error::AppError;
+
+type IniFile = HashMap<String, HashMap<String, String>>;
+
+fn home_dir() -> PathBuf {
+    std::env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
+fn config_file_path() -> PathBuf {
+    std::env::var("AWS_CONFIG_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| home_dir().join(".aws/config"))
+}
+
+fn credentials_file_path() -> PathBuf {
+    std::env::var("AWS_SHARED_CREDENTIALS_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| home_dir().join(".aws/credentials"))
+}
+
+/// Parses the INI-ish format shared by `~/.aws/config` and
+/// `~/.aws/credentials`: `[section]` headers, `key = value` lines, `#`/`;`
+/// comments and blank lines ignored.
+fn parse_ini(path: &PathBuf) -> Result<IniFile, AppError> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| AppError::Config(format!("unable to read {}: {}", path.display(), e)))?;
+
+    let mut sections: IniFile = HashMap::new();
+    let mut current = String::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            current = line[1..line.len() - 1].trim().to_string();
+            sections.entry(current.clone()).or_default();
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            sections
+                .entry(current.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    Ok(sections)
+}
+
+/// Looks up `profile` in a parsed `~/.aws/config`, trying the
+/// `[profile NAME]` convention first and falling back to a bare `[NAME]`
+/// section (the form `default` uses).
+fn config_profile_section<'a>(
+    sections: &'a IniFile,
+    profile: &str,
+) -> Option<&'a HashMap<String, String>> {
+    sections
+        .get(&format!("profile {}", profile))
+        .or_else(|| sections.get(profile))
+}
+
+/// Resolves `region` for `profile` from `~/.aws/config` (or
+/// `$AWS_CONFIG_FILE`).
+pub fn resolve_region(profile: &str) -> Result<String, AppError> {
+    let path = config_file_path();
+    let sections = parse_ini(&path)?;
+    let section = config_profile_section(&sections, profile).ok_or_else(|| {
+        AppError::Config(format!(
+            "profile '{}' not found in {}",
+            profile,
+            path.display()
+        ))
+    })?;
+    section.get("region").cloned().ok_or_else(|| {
+        AppError::Config(format!(
+            "profile '{}' in {} has no region",
+            profile,
+            path.display()
+        ))
+    })
+}
+
+/// Resolves starting credentials for `profile` from `~/.aws/credentials`
+/// (or `$AWS_SHARED_CREDENTIALS_FILE`). Unlike `~/.aws/config`, the
+/// credentials file uses a bare `[NAME]` section for every profile,
+/// including `default`.
+pub fn resolve_credentials(profile: &str) -> Result<Credentials, AppError> {
+    let path = credentials_file_path();
+    let sections = parse_ini(&path)?;
+    let section = sections.get(profile).ok_or_else(|| {
+        AppError::Config(format!(
+            "profile '{}' not found in {}",
+            profile,
+            path.display()
+        ))
+    })?;
+
+    let access_key_id = section.get("aws_access_key_id").ok_or_else(|| {
+        AppError::Config(format!(
+            "profile '{}' in {} has no aws_access_key_id",
+            profile,
+            path.display()
+        ))
+    })?;
+    let secret_access_key = section.get("aws_secret_access_key").ok_or_else(|| {
+        AppError::Config(format!(
+            "profile '{}' in {} has no aws_secret_access_key",
+            profile,
+            path.display()
+        ))
+    })?;
+    let session_token = section.get("aws_session_token").cloned();
+
+    Ok(Credentials::new(
+        access_key_id,
+        secret_access_key,
+        session_token,
+        None,
+        "aws-profile",
+    ))
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use aws_credential_types::Credentials;
+use prettytable::{row, Table};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::images::{cloudwatch_image_download, GetWidgetProps};
+
+/// One query to run as part of a workload, generalizing the single
+/// `--template-path`/`--pattern` pair the `images` subcommand takes into a
+/// batch of many.
+#[derive(Debug, Deserialize, Clone)]
+pub struct WorkloadQuery {
+    pub title: String,
+    pub template_path: PathBuf,
+    #[serde(default)]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub region: Option<String>,
+    pub start: String,
+    pub end: String,
+    pub period: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkloadFile {
+    pub queries: Vec<WorkloadQuery>,
+    #[serde(default = "default_repeat")]
+    pub repeat: usize,
+}
+
+fn default_repeat() -> usize {
+    1
+}
+
+pub fn load_workload(path: &str) -> Result<WorkloadFile, AppError> {
+    let contents = std::fs::read_to_string(path)?;
+    serde_json::from_str(&contents)
+        .map_err(|e| AppError::Config(format!("unable to parse {} as JSON: {}", path, e)))
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct QueryRun {
+    pub title: String,
+    pub latency_ms: u128,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct QueryStats {
+    title: String,
+    count: usize,
+    failures: usize,
+    min_ms: u128,
+    max_ms: u128,
+    mean_ms: u128,
+    p95_ms: u128,
+}
+
+fn stats_for(title: &str, runs: &[&QueryRun]) -> QueryStats {
+    let mut latencies: Vec<u128> = runs.iter().map(|r| r.latency_ms).collect();
+    latencies.sort_unstable();
+
+    let count = latencies.len();
+    let min_ms = *latencies.first().unwrap_or(&0);
+    let max_ms = *latencies.last().unwrap_or(&0);
+    let mean_ms = if count == 0 {
+        0
+    } else {
+        latencies.iter().sum::<u128>() / count as u128
+    };
+    let p95_idx = ((count as f64) * 0.95).ceil() as usize;
+    let p95_ms = latencies
+        .get(p95_idx.saturating_sub(1))
+        .copied()
+        .unwrap_or(max_ms);
+    let failures = runs.iter().filter(|r| !r.success).count();
+
+    QueryStats {
+        title: title.to_string(),
+        count,
+        failures,
+        min_ms,
+        max_ms,
+        mean_ms,
+        p95_ms,
+    }
+}
+
+/// Runs every query in `workload`, `workload.repeat` times each, against
+/// `namespace`/`region` defaults (typically an account's), returning one
+/// [`QueryRun`] per execution.
+pub async fn run_workload(
+    workload: &WorkloadFile,
+    default_namespace: &str,
+    default_region: &str,
+    credentials: Option<Credentials>,
+) -> Vec<QueryRun> {
+    let mut runs = Vec::new();
+
+    for _ in 0..workload.repeat.max(1) {
+        for query in &workload.queries {
+            let props = GetWidgetProps {
+                title: query.title.clone(),
+                region: Some(query.region.clone().unwrap_or_else(|| default_region.to_string())),
+                app_name: query
+                    .namespace
+                    .clone()
+                    .unwrap_or_else(|| default_namespace.to_string()),
+                template_path: query.template_path.clone(),
+                start: query.start.clone(),
+                end: query.end.clone(),
+                period: query.period.clone(),
+                verbose: false,
+            };
+
+            let started = Instant::now();
+            let result = cloudwatch_image_download(props, credentials.clone()).await;
+            let latency = started.elapsed();
+
+            runs.push(QueryRun {
+                title: query.title.clone(),
+                latency_ms: latency.as_millis(),
+                success: result.is_ok(),
+            });
+
+            if let Err(e) = result {
+                println!("batch query '{}' failed: {:?}", query.title, e);
+            }
+        }
+    }
+
+    runs
+}
+
+/// Prints a min/max/mean/p95 report per query title, plus an "overall" row
+/// across every run.
+pub fn print_report(runs: &[QueryRun]) {
+    let mut by_title: HashMap<&str, Vec<&QueryRun>> = HashMap::new();
+    for run in runs {
+        by_title.entry(run.title.as_str()).or_default().push(run);
+    }
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Query", "Count", "Failures", "Min (ms)", "Max (ms)", "Mean (ms)", "p95 (ms)"
+    ]);
+
+    let mut titles: Vec<&str> = by_title.keys().copied().collect();
+    titles.sort_unstable();
+    for title in titles {
+        let stats = stats_for(title, &by_title[title]);
+        table.add_row(row![
+            stats.title,
+            stats.count,
+            stats.failures,
+            stats.min_ms,
+            stats.max_ms,
+            stats.mean_ms,
+            stats.p95_ms
+        ]);
+    }
+
+    let all: Vec<&QueryRun> = runs.iter().collect();
+    let overall = stats_for("overall", &all);
+    table.add_row(row![
+        overall.title,
+        overall.count,
+        overall.failures,
+        overall.min_ms,
+        overall.max_ms,
+        overall.mean_ms,
+        overall.p95_ms
+    ]);
+
+    table.printstd();
+}
+
+pub fn write_results_json(path: &str, runs: &[QueryRun]) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(runs)
+        .map_err(|e| AppError::Config(format!("unable to serialize results: {}", e)))?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
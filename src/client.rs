@@ -0,0 +1,33 @@
+use aws_config::meta::region::RegionProviderChain;
+use aws_credential_types::Credentials;
+use aws_sdk_cloudwatch::{Client, Region};
+
+async fn shared_config(
+    region: Option<String>,
+    credentials: Option<Credentials>,
+) -> aws_types::SdkConfig {
+    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
+        .or_default_provider()
+        .or_else(Region::new("us-west-2"));
+
+    let mut loader = aws_config::from_env().region(region_provider);
+    if let Some(credentials) = credentials {
+        loader = loader.credentials_provider(credentials);
+    }
+    loader.load().await
+}
+
+/// Builds a CloudWatch client for `region`, optionally operating inside a
+/// target account via `credentials` (e.g. from [`crate::creds::load_creds`]).
+pub async fn get_client(region: Option<String>, credentials: Option<Credentials>) -> Client {
+    Client::new(&shared_config(region, credentials).await)
+}
+
+/// Builds a CloudWatch Logs client for `region`, optionally operating
+/// inside a target account via `credentials`.
+pub async fn get_logs_client(
+    region: Option<String>,
+    credentials: Option<Credentials>,
+) -> aws_sdk_cloudwatchlogs::Client {
+    aws_sdk_cloudwatchlogs::Client::new(&shared_config(region, credentials).await)
+}
@@ -0,0 +1,94 @@
+use serde::Deserialize;
+
+use crate::aws_profile;
+use crate::error::AppError;
+
+#[derive(Deserialize, Debug)]
+pub struct AccountsConfig {
+    pub account: Vec<AccountConfig>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountConfig {
+    pub namespace: String,
+    pub account_id: String,
+
+    /// Region to operate in. May be omitted when `profile` is set, in
+    /// which case it's resolved from `~/.aws/config`.
+    #[serde(default)]
+    pub region: Option<String>,
+
+    /// Named AWS profile to resolve `region` (and starting credentials,
+    /// before any `role_name` is assumed) from `~/.aws/config` and
+    /// `~/.aws/credentials`, instead of hard-coding them in this file.
+    #[serde(default)]
+    pub profile: Option<String>,
+
+    /// IAM role to assume in `account_id` before talking to any AWS API.
+    /// Defaults to the `--role-name` CLI flag when omitted from the TOML.
+    #[serde(default)]
+    pub role_name: Option<String>,
+
+    /// `RoleSessionName` passed to `sts:AssumeRole`. Defaults to the
+    /// `--session-name` CLI flag (itself defaulting to `dev-cli`) when
+    /// omitted.
+    #[serde(default)]
+    pub session_name: Option<String>,
+}
+
+impl AccountConfig {
+    /// Returns the configured region, resolving it from `profile` via
+    /// `~/.aws/config` when the TOML omits it.
+    pub fn region(&self) -> Result<String, AppError> {
+        match &self.region {
+            Some(region) => Ok(region.clone()),
+            None => {
+                let profile = self.profile.as_deref().ok_or_else(|| {
+                    AppError::Config(format!(
+                        "account '{}' has no region and no profile to resolve one from",
+                        self.namespace
+                    ))
+                })?;
+                aws_profile::resolve_region(profile)
+            }
+        }
+    }
+}
+
+pub fn get_accounts(filepath: &str, verbose: bool) -> Option<AccountsConfig> {
+    let config_file = std::fs::read_to_string(filepath);
+    if let Ok(contents) = config_file {
+        let accounts_config: AccountsConfig =
+            toml::from_str(&contents).expect("unable to parse as toml");
+        if verbose {
+            for acc in &accounts_config.account {
+                println!("{:?}", acc)
+            }
+        }
+        Some(accounts_config)
+    } else {
+        None
+    }
+}
+
+pub fn filter_accounts(
+    pattern: Option<&str>,
+    accounts: Option<AccountsConfig>,
+) -> Vec<AccountConfig> {
+    if let Some(pat) = pattern {
+        let pat = String::from(pat);
+        let filtered: Vec<AccountConfig> = accounts
+            .unwrap()
+            .account
+            .into_iter()
+            .filter(|x| x.namespace.contains(&pat))
+            .collect();
+        println!("Filtered accounts:");
+        for acc in &filtered {
+            println!("{:?}", &acc);
+        }
+        filtered
+    } else {
+        accounts.expect("expected accounts to filter").account
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use aws_credential_types::Credentials;
+use aws_sdk_sts::{Client as StsClient, Region};
+
+use crate::aws_profile;
+use crate::config::AccountConfig;
+use crate::error::AppError;
+
+/// Refresh cached credentials once less than this much time remains before
+/// `Expiration`, so a long run over many accounts doesn't fail mid-way.
+const REFRESH_SKEW: Duration = Duration::from_secs(60);
+
+struct CachedCredentials {
+    credentials: Credentials,
+    expires_at: SystemTime,
+}
+
+/// Per-account cache of assumed-role credentials.
+///
+/// `load` assumes `arn:aws:iam::{account_id}:role/{role_name}` the first
+/// time an account is seen, then serves the cached `Credentials` until
+/// they're close to `Expiration`, at which point it assumes the role again.
+#[derive(Default)]
+pub struct CredentialCache {
+    cache: Mutex<HashMap<String, CachedCredentials>>,
+}
+
+impl CredentialCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn load(
+        &self,
+        account: &AccountConfig,
+        role_name: &str,
+        session_name: &str,
+    ) -> Result<Credentials, AppError> {
+        let cache_key = format!("{}:{}", account.account_id, role_name);
+
+        if let Some(cached) = self.cache.lock().unwrap().get(&cache_key) {
+            if cached.expires_at > SystemTime::now() + REFRESH_SKEW {
+                return Ok(cached.credentials.clone());
+            }
+        }
+
+        let (credentials, expires_at) = assume_role(account, role_name, session_name).await?;
+        self.cache.lock().unwrap().insert(
+            cache_key,
+            CachedCredentials {
+                credentials: credentials.clone(),
+                expires_at,
+            },
+        );
+        Ok(credentials)
+    }
+}
+
+/// Assumes `role_name` in `account.account_id`, starting from whatever base
+/// credentials can be found: the account's `profile` in `~/.aws/credentials`
+/// if set, otherwise whatever `aws_config::from_env()` finds (environment
+/// variables, the default profile, or web identity federation), so the same
+/// code works in CI and on a developer machine.
+async fn assume_role(
+    account: &AccountConfig,
+    role_name: &str,
+    session_name: &str,
+) -> Result<(Credentials, SystemTime), AppError> {
+    let region = account.region()?;
+    let mut loader = aws_config::from_env().region(Region::new(region));
+    if let Some(profile) = &account.profile {
+        loader = loader.credentials_provider(aws_profile::resolve_credentials(profile)?);
+    }
+    let base_config = loader.load().await;
+    let sts = StsClient::new(&base_config);
+
+    let role_arn = format!("arn:aws:iam::{}:role/{}", account.account_id, role_name);
+
+    let resp = sts
+        .assume_role()
+        .role_arn(&role_arn)
+        .role_session_name(session_name)
+        .send()
+        .await
+        .map_err(aws_sdk_sts::Error::from)?;
+
+    let creds = resp
+        .credentials()
+        .ok_or_else(|| AppError::Config(format!("assume_role({}) returned no credentials", role_arn)))?;
+    let expiration = creds.expiration().ok_or_else(|| {
+        AppError::Config(format!("assume_role({}) returned no expiration", role_arn))
+    })?;
+    let expires_at = SystemTime::UNIX_EPOCH + Duration::from_secs(expiration.secs().max(0) as u64);
+
+    let credentials = Credentials::new(
+        creds.access_key_id().unwrap_or_default(),
+        creds.secret_access_key().unwrap_or_default(),
+        Some(creds.session_token().unwrap_or_default().to_string()),
+        Some(expires_at),
+        "sts-assume-role",
+    );
+
+    Ok((credentials, expires_at))
+}
+
+/// Resolves credentials for `account`, falling back to `default_role_name`
+/// and `default_session_name` (the `--role-name`/`--session-name` CLI
+/// flags) when the account's TOML table doesn't set its own.
+pub async fn load_creds(
+    cache: &CredentialCache,
+    account: &AccountConfig,
+    default_role_name: &str,
+    default_session_name: &str,
+) -> Result<Credentials, AppError> {
+    let role_name = account.role_name.as_deref().unwrap_or(default_role_name);
+    let session_name = account
+        .session_name
+        .as_deref()
+        .unwrap_or(default_session_name);
+    cache.load(account, role_name, session_name).await
+}
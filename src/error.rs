@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Top-level error type for the `dev` CLI.
+///
+/// The tool talks to several AWS services (CloudWatch, STS, and friends),
+/// each of which has its own generated `Error` enum. Rather than threading
+/// a different `Result` through every subcommand, we fold all of them into
+/// this one type and implement `From` for each service error we touch.
+#[derive(Debug)]
+pub enum AppError {
+    CloudWatch(aws_sdk_cloudwatch::Error),
+    CloudWatchLogs(aws_sdk_cloudwatchlogs::Error),
+    Sts(aws_sdk_sts::Error),
+    Config(String),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::CloudWatch(e) => write!(f, "cloudwatch error: {}", e),
+            AppError::CloudWatchLogs(e) => write!(f, "cloudwatch logs error: {}", e),
+            AppError::Sts(e) => write!(f, "sts error: {}", e),
+            AppError::Config(msg) => write!(f, "config error: {}", msg),
+            AppError::Io(e) => write!(f, "io error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<aws_sdk_cloudwatch::Error> for AppError {
+    fn from(e: aws_sdk_cloudwatch::Error) -> Self {
+        AppError::CloudWatch(e)
+    }
+}
+
+impl From<aws_sdk_cloudwatchlogs::Error> for AppError {
+    fn from(e: aws_sdk_cloudwatchlogs::Error) -> Self {
+        AppError::CloudWatchLogs(e)
+    }
+}
+
+impl From<aws_sdk_sts::Error> for AppError {
+    fn from(e: aws_sdk_sts::Error) -> Self {
+        AppError::Sts(e)
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::Io(e)
+    }
+}
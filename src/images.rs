@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use aws_config::meta::region::RegionProviderChain;
+use aws_credential_types::Credentials;
+use aws_sdk_cloudwatch::{Region, PKG_VERSION};
+use tokio::fs;
+
+use crate::client::get_client;
+use crate::error::AppError;
+
+#[derive(Debug)]
+pub struct GetWidgetProps {
+    pub region: Option<String>,
+
+    pub app_name: String,
+
+    pub title: String,
+
+    pub verbose: bool,
+
+    pub template_path: PathBuf,
+
+    pub start: String,
+
+    pub end: String,
+
+    pub period: String,
+}
+
+pub async fn cloudwatch_image_download(
+    opts: GetWidgetProps,
+    credentials: Option<Credentials>,
+) -> Result<(), AppError> {
+    let GetWidgetProps {
+        title,
+        region,
+        app_name: namespace,
+        verbose,
+        template_path: filepath,
+        start,
+        end,
+        period,
+    } = opts;
+
+    let replaced_region = region.clone().unwrap_or_else(|| String::from("us-west-2"));
+
+    let region_provider = RegionProviderChain::first_try(region.clone().map(Region::new))
+        .or_default_provider()
+        .or_else(Region::new("us-west-2"));
+
+    if verbose {
+        println!();
+        println!("CloudWatch client version: {}", PKG_VERSION);
+        println!(
+            "Region:                    {}",
+            region_provider.region().await.unwrap().as_ref()
+        );
+        println!();
+    }
+
+    let client = get_client(region, credentials).await;
+    if let Some(metrics) = get_metrics_json(
+        &filepath,
+        &replaced_region,
+        &namespace,
+        &start,
+        &end,
+        &period,
+        verbose,
+    ) {
+        let saved_image_name = format!(
+            "{}-{}-{}-{}-{}",
+            &namespace,
+            &title,
+            &replaced_region,
+            &start,
+            std::time::SystemTime::now()
+                .duration_since(std::time::SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        );
+        get_metric_image(&client, metrics.as_ref(), &saved_image_name).await
+    } else {
+        Err(AppError::Config(format!(
+            "unable to read or template {}",
+            filepath.display()
+        )))
+    }
+}
+
+pub fn get_metrics_json(
+    filepath: &PathBuf,
+    region: &str,
+    namespace: &str,
+    start: &str,
+    end: &str,
+    period: &str,
+    verbose: bool,
+) -> Option<String> {
+    let template_file = std::fs::read_to_string(filepath);
+    if let Ok(contents) = template_file {
+        let mut template_params = HashMap::<&str, &str>::new();
+
+        // TODO: make this configurable
+        template_params.insert("{{NAMESPACE}}", namespace);
+        template_params.insert("{{REGION}}", region);
+        // format: 4320H
+        template_params.insert("{{PERIOD_START}}", start);
+        template_params.insert("{{PERIOD_END}}", end);
+        template_params.insert("{{PERIOD}}", period);
+
+        let mut replaced = contents;
+        template_params
+            .iter()
+            .for_each(|(k, v)| replaced = replaced.replace(k, v));
+
+        if verbose {
+            println!("templated:\n{}", &replaced);
+        }
+
+        Some(replaced)
+    } else {
+        None
+    }
+}
+
+/// Calls AWS CloudWatch GetMetricImage API and downloads locally
+/// API Reference: [GetMetricWidgetImage](https://docs.aws.amazon.com/AmazonCloudWatch/latest/APIReference/API_GetMetricWidgetImage.html)
+pub async fn get_metric_image(
+    client: &aws_sdk_cloudwatch::Client,
+    metric_json: &str,
+    saved_image_name: &str,
+) -> Result<(), AppError> {
+    println!("getting metric image");
+
+    let request = client
+        .get_metric_widget_image()
+        .output_format("png")
+        .set_metric_widget(Some(String::from(metric_json)));
+    let resp = request.send().await.map_err(aws_sdk_cloudwatch::Error::from)?;
+
+    if let Some(blob) = resp.metric_widget_image {
+        let path = Path::new(saved_image_name).with_extension("png");
+
+        // convert to base64 encoded byte vector
+        let base64_encoded = blob.into_inner();
+
+        // wait to finish saving file
+        let res = fs::write(path, base64_encoded).await;
+        match res {
+            Ok(()) => {
+                println!("saved metric image");
+            }
+            Err(e) => {
+                println!("error writing to file: {:?}", e);
+            }
+        }
+    } else {
+        println!("error getting metric image");
+    }
+    Ok(())
+}
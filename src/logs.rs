@@ -0,0 +1,155 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use aws_sdk_cloudwatchlogs::model::QueryStatus;
+use aws_sdk_cloudwatchlogs::Client;
+
+use crate::error::AppError;
+use crate::time::parse_relative_time;
+
+/// Prints every log group, optionally restricted to those starting with
+/// `prefix`.
+pub async fn list_log_groups(client: &Client, prefix: Option<&str>) -> Result<(), AppError> {
+    let mut next_token = None;
+
+    loop {
+        let resp = client
+            .describe_log_groups()
+            .set_log_group_name_prefix(prefix.map(String::from))
+            .set_next_token(next_token.clone())
+            .send()
+            .await
+            .map_err(aws_sdk_cloudwatchlogs::Error::from)?;
+
+        for group in resp.log_groups().unwrap_or_default() {
+            println!("{}", group.log_group_name().unwrap_or_default());
+        }
+
+        next_token = resp.next_token().map(String::from);
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs `FilterLogEvents` against `log_group` over `[start, end)`, printing
+/// each matching event's timestamp and message.
+pub async fn filter_log_events(
+    client: &Client,
+    log_group: &str,
+    filter_pattern: Option<&str>,
+    start: &str,
+    end: &str,
+) -> Result<(), AppError> {
+    let start_ms = parse_relative_time(start)?
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let end_ms = parse_relative_time(end)?
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+
+    let mut next_token = None;
+
+    loop {
+        let resp = client
+            .filter_log_events()
+            .log_group_name(log_group)
+            .set_filter_pattern(filter_pattern.map(String::from))
+            .start_time(start_ms)
+            .end_time(end_ms)
+            .set_next_token(next_token.clone())
+            .send()
+            .await
+            .map_err(aws_sdk_cloudwatchlogs::Error::from)?;
+
+        for event in resp.events().unwrap_or_default() {
+            println!(
+                "[{}] {}",
+                event.timestamp().unwrap_or_default(),
+                event.message().unwrap_or_default()
+            );
+        }
+
+        next_token = resp.next_token().map(String::from);
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs a Logs Insights `query` over `log_group`, polling
+/// `GetQueryResults` until the query completes, then prints the result
+/// rows.
+pub async fn run_insights_query(
+    client: &Client,
+    log_group: &str,
+    query: &str,
+    start: &str,
+    end: &str,
+) -> Result<(), AppError> {
+    let start_secs = parse_relative_time(start)?
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let end_secs = parse_relative_time(end)?
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    let start_resp = client
+        .start_query()
+        .log_group_name(log_group)
+        .query_string(query)
+        .start_time(start_secs)
+        .end_time(end_secs)
+        .send()
+        .await
+        .map_err(aws_sdk_cloudwatchlogs::Error::from)?;
+
+    let query_id = start_resp
+        .query_id()
+        .ok_or_else(|| AppError::Config("StartQuery returned no query_id".into()))?;
+
+    loop {
+        let results = client
+            .get_query_results()
+            .query_id(query_id)
+            .send()
+            .await
+            .map_err(aws_sdk_cloudwatchlogs::Error::from)?;
+
+        match results.status() {
+            Some(QueryStatus::Complete) => {
+                for row in results.results().unwrap_or_default() {
+                    let line: Vec<String> = row
+                        .iter()
+                        .map(|field| {
+                            format!(
+                                "{}={}",
+                                field.field().unwrap_or_default(),
+                                field.value().unwrap_or_default()
+                            )
+                        })
+                        .collect();
+                    println!("{}", line.join(", "));
+                }
+                break;
+            }
+            Some(QueryStatus::Failed) | Some(QueryStatus::Cancelled) => {
+                return Err(AppError::Config(format!(
+                    "insights query {} did not complete: {:?}",
+                    query_id,
+                    results.status()
+                )));
+            }
+            _ => tokio::time::sleep(Duration::from_secs(1)).await,
+        }
+    }
+
+    Ok(())
+}
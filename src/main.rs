@@ -1,64 +1,51 @@
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use clap::{Arg, Command};
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_cloudwatch::{Client, Error, Region, PKG_VERSION};
-use serde::Deserialize;
-use tokio::fs;
-
-#[derive(Deserialize, Debug)]
-struct AccountsConfig {
-    account: Vec<AccountConfig>,
-}
-
-#[derive(Deserialize, Debug)]
-struct AccountConfig {
-    namespace: String,
-    account_id: String,
-    region: String,
-}
-
-#[derive(Debug)]
-struct GetWidgetProps {
-    region: Option<String>,
-
-    app_name: String,
-
-    title: String,
-
-    verbose: bool,
-
-    template_path: PathBuf,
-
-    start: String,
-
-    end: String,
-
-    period: String,
-}
+mod alarms;
+mod aws_profile;
+mod batch;
+mod client;
+mod config;
+mod creds;
+mod error;
+mod images;
+mod logs;
+mod put;
+mod show;
+mod time;
+
+use alarms::{delete_alarms, list_alarms, load_alarm_specs, put_alarms};
+use batch::{load_workload, print_report, run_workload, write_results_json};
+use client::{get_client, get_logs_client};
+use config::{filter_accounts, get_accounts};
+use creds::{load_creds, CredentialCache};
+use error::AppError;
+use images::{cloudwatch_image_download, GetWidgetProps};
+use logs::{filter_log_events, list_log_groups, run_insights_query};
+use put::{load_metric_inputs_from_file, put_metrics, MetricInput};
+use show::{show_metrics, watch_metrics};
 
 /// Dev CLI for repetitive AWS account tasks
 ///
 /// ## Accounts Config
-/// 
+///
 /// The accounts are defined in [TOML](https://toml.io) syntax. The file should be a list of tables containing `namespace`, `account_id`, and `region` for each account.
-/// 
+///
 /// Example (from the repo's accounts.toml):
-/// 
+///
 /// ```toml
 /// [[account]]
 /// namespace = "SomeDataProcessingProgram"
 /// account_id = "111111111111"
 /// region = "us-east-1"
 /// ```
-/// 
+///
 /// To validate accounts config is parsed properly:
-/// 
+///
 /// ```bash
 /// cargo run -- config <ACCOUNT.TOML FILE>
-/// 
+///
 /// # example
 /// cargo run -- config accounts.toml
 /// AccountConfig { namespace: "SomeDataProcessingProgram", account_id: "111111111111", region: "us-east-1" }
@@ -66,21 +53,21 @@ struct GetWidgetProps {
 /// AccountConfig { namespace: "SomeDataProcessingProgram", account_id: "222222222222", region: "us-west-2" }
 /// ...
 /// ```
-/// 
+///
 /// ## Commands
-/// 
+///
 /// You can use `cargo run --` to build and pass commands to the CLI.
-/// 
+///
 /// ```bash
 /// # run retry counts, replace START_TIME in retry-counts graph to start 6 months ago
 /// cargo run -- images --period 3600 --pattern ItemDPP -s 4320H ./resources/traffic.json ../accounts.toml
-/// 
+///
 /// # omit the pattern to run this command for all accounts
 /// cargo run -- images --period 3600  -s 7200H ./resources/traffic.json ../accounts.toml
 /// ```
 
 #[tokio::main]
-async fn main() -> Result<(), Error> {
+async fn main() -> Result<(), AppError> {
     tracing_subscriber::fmt::init();
 
     let matches = Command::new("dev")
@@ -140,6 +127,20 @@ async fn main() -> Result<(), Error> {
                         .required(false)
                         .long("output-path")
                         .short('o'),
+                )
+                .arg(
+                    Arg::new("role-name")
+                        .long("role-name")
+                        .help("IAM role to assume in each account, unless overridden by the account's `role_name`")
+                        .default_value("OrganizationAccountAccessRole")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("session-name")
+                        .long("session-name")
+                        .help("RoleSessionName passed to sts:AssumeRole")
+                        .default_value("dev-cli")
+                        .takes_value(true),
                 ),
         )
         .subcommand(
@@ -153,7 +154,266 @@ async fn main() -> Result<(), Error> {
                         .short('f'),
                 ),
         )
-        .subcommand(Command::new("show").about("show metrics for an account"))
+        .subcommand(
+            Command::new("show")
+                .about("show metrics for an account")
+                .arg(
+                    Arg::new("pattern")
+                        .long("pattern")
+                        .takes_value(true)
+                        .short('f'),
+                )
+                .arg(
+                    Arg::new("watch")
+                        .long("watch")
+                        .help("continuously re-render the table")
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::new("interval")
+                        .long("interval")
+                        .help("seconds between refreshes when --watch is set")
+                        .default_value("5")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("with-values")
+                        .long("with-values")
+                        .help("fetch the latest GetMetricData value for each row")
+                        .takes_value(false),
+                ),
+        )
+        .subcommand(
+            Command::new("put")
+                .about("publish custom metrics via PutMetricData")
+                .arg(
+                    Arg::new("config-path")
+                        .required(true)
+                        .help("the path to the TOML config file with accounts"),
+                )
+                .arg(
+                    Arg::new("pattern")
+                        .long("pattern")
+                        .takes_value(true)
+                        .short('f'),
+                )
+                .arg(
+                    Arg::new("name")
+                        .long("name")
+                        .help("metric name")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("value")
+                        .long("value")
+                        .help("single counter/gauge value")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("values")
+                        .long("values")
+                        .help("comma-separated distribution values")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("counts")
+                        .long("counts")
+                        .help("comma-separated sample counts, aligned with --values")
+                        .takes_value(true),
+                )
+                .arg(Arg::new("unit").long("unit").takes_value(true))
+                .arg(
+                    Arg::new("dimension")
+                        .long("dimension")
+                        .help("dimension as name=value, may be repeated")
+                        .takes_value(true)
+                        .multiple_occurrences(true),
+                )
+                .arg(
+                    Arg::new("timestamp")
+                        .long("timestamp")
+                        .help("unix timestamp in seconds, defaults to now")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("input-file")
+                        .long("input-file")
+                        .help("JSON file containing an array of metric inputs")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("period")
+                        .short('p')
+                        .long("period")
+                        .default_value("3600")
+                        .help("aggregation period in seconds; timestamps are rounded down to this boundary")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("role-name")
+                        .long("role-name")
+                        .default_value("OrganizationAccountAccessRole")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("session-name")
+                        .long("session-name")
+                        .default_value("dev-cli")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            Command::new("alarms")
+                .about("manage CloudWatch alarms across accounts")
+                .arg(
+                    Arg::new("config-path")
+                        .required(true)
+                        .help("the path to the TOML config file with accounts"),
+                )
+                .arg(
+                    Arg::new("pattern")
+                        .long("pattern")
+                        .takes_value(true)
+                        .short('f'),
+                )
+                .arg(
+                    Arg::new("spec-file")
+                        .long("spec-file")
+                        .help("TOML or JSON file describing alarms to put")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("list")
+                        .long("list")
+                        .help("print existing alarms and their state")
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::new("delete")
+                        .long("delete")
+                        .help("alarm name(s) to delete")
+                        .takes_value(true)
+                        .multiple_values(true),
+                )
+                .arg(
+                    Arg::new("role-name")
+                        .long("role-name")
+                        .default_value("OrganizationAccountAccessRole")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("session-name")
+                        .long("session-name")
+                        .default_value("dev-cli")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            Command::new("logs")
+                .about("search CloudWatch Logs across accounts")
+                .arg(
+                    Arg::new("config-path")
+                        .required(true)
+                        .help("the path to the TOML config file with accounts"),
+                )
+                .arg(
+                    Arg::new("pattern")
+                        .long("pattern")
+                        .help("account namespace filter")
+                        .takes_value(true)
+                        .short('f'),
+                )
+                .arg(
+                    Arg::new("list-groups")
+                        .long("list-groups")
+                        .help("list log groups instead of searching")
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::new("log-group")
+                        .long("log-group")
+                        .help("log group name (or prefix, for --list-groups)")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("filter-pattern")
+                        .long("filter-pattern")
+                        .help("FilterLogEvents filter pattern")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("query")
+                        .long("query")
+                        .help("Logs Insights query string; runs StartQuery/GetQueryResults instead of FilterLogEvents")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("start-time")
+                        .short('s')
+                        .long("start-time")
+                        .alias("start")
+                        .default_value("4320H")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("end-time")
+                        .short('e')
+                        .long("end-time")
+                        .alias("end")
+                        .default_value("0H")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("role-name")
+                        .long("role-name")
+                        .default_value("OrganizationAccountAccessRole")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("session-name")
+                        .long("session-name")
+                        .default_value("dev-cli")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            Command::new("batch")
+                .about("run a workload file of many widget/metric queries and report timing")
+                .arg(
+                    Arg::new("workload-file")
+                        .required(true)
+                        .help("JSON file describing the queries to run"),
+                )
+                .arg(
+                    Arg::new("config-path")
+                        .required(true)
+                        .help("the path to the TOML config file with accounts"),
+                )
+                .arg(
+                    Arg::new("pattern")
+                        .long("pattern")
+                        .takes_value(true)
+                        .short('f'),
+                )
+                .arg(
+                    Arg::new("output-file")
+                        .long("output-file")
+                        .help("write per-run results as JSON to this path")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("role-name")
+                        .long("role-name")
+                        .default_value("OrganizationAccountAccessRole")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("session-name")
+                        .long("session-name")
+                        .default_value("dev-cli")
+                        .takes_value(true),
+                ),
+        )
         .get_matches();
 
     match matches.subcommand() {
@@ -165,252 +425,217 @@ async fn main() -> Result<(), Error> {
             let title = images.value_of("title").unwrap();
             let config_path = images.value_of("config-path").unwrap();
             let pattern = images.value_of("pattern");
+            let role_name = images.value_of("role-name").unwrap();
+            let session_name = images.value_of("session-name").unwrap();
             let accounts = get_accounts(config_path, true);
             let accounts = filter_accounts(pattern, accounts);
 
+            let cred_cache = CredentialCache::new();
+
             for acc in accounts {
-                load_creds(&acc);
+                let credentials = load_creds(&cred_cache, &acc, role_name, session_name).await?;
                 let props = GetWidgetProps {
                     title: String::from(title),
-                    region: Some(acc.region),
-                    app_name: acc.namespace,
+                    region: Some(acc.region()?),
+                    app_name: acc.namespace.clone(),
                     template_path: PathBuf::from(template_path),
                     start: String::from(start),
                     end: String::from(end),
                     period: String::from(period),
                     verbose: true,
                 };
-                match cloudwatch_image_download(props).await {
+                match cloudwatch_image_download(props, Some(credentials)).await {
                     Ok(_) => println!("successful query"),
                     Err(e) => println!("cloudwatch download error: {:?}", e),
                 };
             }
         }
-        Some(("show", show_matches)) => {
-            println!("show: {:?}", show_matches);
+        Some(("put", put_matches)) => {
+            let config_path = put_matches.value_of("config-path").unwrap();
+            let pattern = put_matches.value_of("pattern");
+            let period: i64 = put_matches
+                .value_of("period")
+                .unwrap()
+                .parse()
+                .expect("period must be an integer number of seconds");
+            let role_name = put_matches.value_of("role-name").unwrap();
+            let session_name = put_matches.value_of("session-name").unwrap();
+
+            let inputs = if let Some(input_file) = put_matches.value_of("input-file") {
+                load_metric_inputs_from_file(input_file)?
+            } else {
+                let name = put_matches
+                    .value_of("name")
+                    .expect("--name or --input-file is required")
+                    .to_string();
+                let values: Vec<f64> = put_matches
+                    .value_of("values")
+                    .map(|v| v.split(',').map(|p| p.parse().unwrap()).collect())
+                    .unwrap_or_default();
+                let counts: Vec<f64> = put_matches
+                    .value_of("counts")
+                    .map(|v| v.split(',').map(|p| p.parse().unwrap()).collect())
+                    .unwrap_or_default();
+                let value = put_matches.value_of("value").map(|v| v.parse().unwrap());
+                let unit = put_matches.value_of("unit").map(String::from);
+                let timestamp = put_matches.value_of("timestamp").map(|v| v.parse().unwrap());
+                let dimensions = put_matches
+                    .values_of("dimension")
+                    .map(|vals| {
+                        vals.map(|d| {
+                            let (k, v) = d.split_once('=').expect("dimension must be name=value");
+                            (k.to_string(), v.to_string())
+                        })
+                        .collect()
+                    })
+                    .unwrap_or_default();
+
+                vec![MetricInput {
+                    name,
+                    value,
+                    values,
+                    counts,
+                    unit,
+                    dimensions,
+                    timestamp,
+                }]
+            };
 
-            let client = get_client(Some(String::from("us-west-2"))).await;
-            let res = show_metrics(&client).await;
-            if res.is_err() {
-                println!("encountered error getting metrics: {:?}", res.err());
-            }
-        }
-        Some(("config", config)) => {
-            let config_path = config.value_of("config-path").unwrap();
-            let pattern = config.value_of("pattern");
             let accounts = get_accounts(config_path, true);
-            let _filtered = filter_accounts(pattern, accounts);
-        }
-        _ => unreachable!(),
-    };
-
-    Ok(())
-}
-
-fn filter_accounts(pattern: Option<&str>, accounts: Option<AccountsConfig>) -> Vec<AccountConfig> {
-    if let Some(pat) = pattern {
-        let pat = String::from(pat);
-        let filtered: Vec<AccountConfig> = accounts
-            .unwrap()
-            .account
-            .into_iter()
-            .filter(|x| x.namespace.contains(&pat))
-            .collect();
-        println!("Filtered accounts:");
-        for acc in &filtered {
-            println!("{:?}", &acc);
-        }
-        filtered
-    } else {
-        accounts.expect("expected accounts to filter").account
-    }
-}
-
-async fn get_client(region: Option<String>) -> Client {
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    Client::new(&shared_config)
-}
-
-async fn cloudwatch_image_download(opts: GetWidgetProps) -> Result<(), Error> {
-    let GetWidgetProps {
-        title,
-        region,
-        app_name: namespace,
-        verbose,
-        template_path: filepath,
-        start,
-        end,
-        period,
-    } = opts;
-
-    let replaced_region = region.clone().unwrap_or_else(|| String::from("us-west-2"));
-
-    let region_provider = RegionProviderChain::first_try(region.clone().map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    if verbose {
-        println!();
-        println!("CloudWatch client version: {}", PKG_VERSION);
-        println!(
-            "Region:                    {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    // let shared_config = aws_config::from_env().region(region_provider).load().await;
-    // let client = Client::new(&shared_config);
-    let client = get_client(region).await;
-    if let Some(metrics) = get_metrics_json(
-        &filepath,
-        &replaced_region,
-        &namespace,
-        &start,
-        &end,
-        &period,
-        verbose,
-    ) {
-        let saved_image_name = format!(
-            "{}-{}-{}-{}-{}",
-            &namespace,
-            &title,
-            &replaced_region,
-            &start,
-            std::time::SystemTime::now()
-                .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
-        );
-        get_metric_image(&client, metrics.as_ref(), &saved_image_name).await
-    } else {
-        panic!("unable to parse metrics json")
-    }
-}
+            let accounts = filter_accounts(pattern, accounts);
+            let cred_cache = CredentialCache::new();
 
-fn get_accounts(filepath: &str, verbose: bool) -> Option<AccountsConfig> {
-    let config_file = std::fs::read_to_string(filepath);
-    if let Ok(contents) = config_file {
-        let accounts_config: AccountsConfig =
-            toml::from_str(&contents).expect("unable to parse as toml");
-        if verbose {
-            // println!("parsed config toml: \n {:?}", &accounts_config);
-            for acc in &accounts_config.account {
-                println!("{:?}", acc)
+            for acc in accounts {
+                let credentials = load_creds(&cred_cache, &acc, role_name, session_name).await?;
+                let client = get_client(Some(acc.region()?), Some(credentials)).await;
+                match put_metrics(&client, &acc.namespace, &inputs, period).await {
+                    Ok(_) => println!("published {} metric(s) to {}", inputs.len(), acc.namespace),
+                    Err(e) => println!("put_metric_data error: {:?}", e),
+                };
             }
         }
-        Some(accounts_config)
-    } else {
-        None
-    }
-}
-
-fn get_metrics_json(
-    filepath: &PathBuf,
-    region: &str,
-    namespace: &str,
-    start: &str,
-    end: &str,
-    period: &str,
-    verbose: bool,
-) -> Option<String> {
-    let template_file = std::fs::read_to_string(filepath);
-    if let Ok(contents) = template_file {
-        let mut template_params = HashMap::<&str, &str>::new();
+        Some(("alarms", alarms_matches)) => {
+            let config_path = alarms_matches.value_of("config-path").unwrap();
+            let pattern = alarms_matches.value_of("pattern");
+            let role_name = alarms_matches.value_of("role-name").unwrap();
+            let session_name = alarms_matches.value_of("session-name").unwrap();
+            let delete_names: Vec<String> = alarms_matches
+                .values_of("delete")
+                .map(|vals| vals.map(String::from).collect())
+                .unwrap_or_default();
+
+            let specs = match alarms_matches.value_of("spec-file") {
+                Some(spec_file) => load_alarm_specs(spec_file)?,
+                None => Vec::new(),
+            };
 
-        // TODO: make this configurable
-        template_params.insert("{{NAMESPACE}}", namespace);
-        template_params.insert("{{REGION}}", region);
-        // format: 4320H
-        template_params.insert("{{PERIOD_START}}", start);
-        template_params.insert("{{PERIOD_END}}", end);
-        template_params.insert("{{PERIOD}}", period);
-
-        let mut replaced = contents;
-        template_params
-            .iter()
-            .for_each(|(k, v)| replaced = replaced.replace(k, v));
+            let accounts = get_accounts(config_path, true);
+            let accounts = filter_accounts(pattern, accounts);
+            let cred_cache = CredentialCache::new();
 
-        if verbose {
-            println!("templated:\n{}", &replaced);
+            for acc in accounts {
+                let credentials = load_creds(&cred_cache, &acc, role_name, session_name).await?;
+                let client = get_client(Some(acc.region()?), Some(credentials)).await;
+
+                if alarms_matches.is_present("list") {
+                    list_alarms(&client).await?;
+                }
+                if !delete_names.is_empty() {
+                    delete_alarms(&client, &delete_names).await?;
+                }
+                if !specs.is_empty() {
+                    put_alarms(&client, &specs).await?;
+                }
+            }
         }
+        Some(("logs", logs_matches)) => {
+            let config_path = logs_matches.value_of("config-path").unwrap();
+            let pattern = logs_matches.value_of("pattern");
+            let role_name = logs_matches.value_of("role-name").unwrap();
+            let session_name = logs_matches.value_of("session-name").unwrap();
+            let start = logs_matches.value_of("start-time").unwrap();
+            let end = logs_matches.value_of("end-time").unwrap();
+            let log_group = logs_matches.value_of("log-group");
+            let filter_pattern = logs_matches.value_of("filter-pattern");
+            let query = logs_matches.value_of("query");
 
-        Some(replaced)
-    } else {
-        None
-    }
-}
-
-// List metrics.
-async fn show_metrics(
-    client: &aws_sdk_cloudwatch::Client,
-) -> Result<(), aws_sdk_cloudwatch::Error> {
-    let rsp = client.list_metrics().send().await?;
-    let metrics = rsp.metrics().unwrap_or_default();
-
-    let num_metrics = metrics.len();
-
-    for metric in metrics {
-        println!("Namespace: {}", metric.namespace().unwrap_or_default());
-        println!("Name:      {}", metric.metric_name().unwrap_or_default());
-        println!("Dimensions:");
+            let accounts = get_accounts(config_path, true);
+            let accounts = filter_accounts(pattern, accounts);
+            let cred_cache = CredentialCache::new();
 
-        if let Some(dimension) = metric.dimensions.as_ref() {
-            for d in dimension {
-                println!("  Name:  {}", d.name().unwrap_or_default());
-                println!("  Value: {}", d.value().unwrap_or_default());
-                println!();
+            for acc in accounts {
+                let credentials = load_creds(&cred_cache, &acc, role_name, session_name).await?;
+                let client = get_logs_client(Some(acc.region()?), Some(credentials)).await;
+
+                if logs_matches.is_present("list-groups") {
+                    list_log_groups(&client, log_group).await?;
+                    continue;
+                }
+
+                let log_group = log_group.expect("--log-group is required unless --list-groups is set");
+                if let Some(query) = query {
+                    run_insights_query(&client, log_group, query, start, end).await?;
+                } else {
+                    filter_log_events(&client, log_group, filter_pattern, start, end).await?;
+                }
             }
         }
+        Some(("batch", batch_matches)) => {
+            let workload_file = batch_matches.value_of("workload-file").unwrap();
+            let config_path = batch_matches.value_of("config-path").unwrap();
+            let pattern = batch_matches.value_of("pattern");
+            let output_file = batch_matches.value_of("output-file");
+            let role_name = batch_matches.value_of("role-name").unwrap();
+            let session_name = batch_matches.value_of("session-name").unwrap();
+
+            let workload = load_workload(workload_file)?;
+            let accounts = get_accounts(config_path, true);
+            let accounts = filter_accounts(pattern, accounts);
+            let cred_cache = CredentialCache::new();
 
-        println!();
-    }
-
-    println!("Found {} metrics.", num_metrics);
-
-    Ok(())
-}
-
-/// Calls AWS CloudWatch GetMetricImage API and downloads locally
-/// API Reference: [GetMetricWidgetImage](https://docs.aws.amazon.com/AmazonCloudWatch/latest/APIReference/API_GetMetricWidgetImage.html)
-async fn get_metric_image(
-    client: &aws_sdk_cloudwatch::Client,
-    metric_json: &str,
-    saved_image_name: &str,
-) -> Result<(), aws_sdk_cloudwatch::Error> {
-    println!("getting metric image");
-
-    let request = client
-        .get_metric_widget_image()
-        .output_format("png")
-        .set_metric_widget(Some(String::from(metric_json)));
-    let resp = request.send().await?;
-
-    if let Some(blob) = resp.metric_widget_image {
-        let path = Path::new(saved_image_name).with_extension("png");
-
-        // convert to base64 encoded byte vector
-        let base64_encoded = blob.into_inner();
+            let mut all_runs = Vec::new();
+            for acc in accounts {
+                let credentials = load_creds(&cred_cache, &acc, role_name, session_name).await?;
+                let runs =
+                    run_workload(&workload, &acc.namespace, &acc.region()?, Some(credentials)).await;
+                all_runs.extend(runs);
+            }
 
-        // wait to finish saving file
-        let res = fs::write(path, base64_encoded).await;
-        match res {
-            Ok(()) => {
-                println!("saved metric image");
+            print_report(&all_runs);
+            if let Some(output_file) = output_file {
+                write_results_json(output_file, &all_runs)?;
             }
-            Err(e) => {
-                println!("error writing to file: {:?}", e);
+        }
+        Some(("show", show_matches)) => {
+            let pattern = show_matches.value_of("pattern");
+            let with_values = show_matches.is_present("with-values");
+            let client = get_client(Some(String::from("us-west-2")), None).await;
+
+            if show_matches.is_present("watch") {
+                let interval_secs: u64 = show_matches
+                    .value_of("interval")
+                    .unwrap()
+                    .parse()
+                    .expect("interval must be an integer number of seconds");
+                watch_metrics(
+                    &client,
+                    pattern,
+                    std::time::Duration::from_secs(interval_secs),
+                    with_values,
+                )
+                .await?;
+            } else {
+                show_metrics(&client, pattern, with_values).await?;
             }
         }
-    } else {
-        println!("error getting metric image");
-    }
-    Ok(())
-}
+        Some(("config", config)) => {
+            let config_path = config.value_of("config-path").unwrap();
+            let pattern = config.value_of("pattern");
+            let accounts = get_accounts(config_path, true);
+            let _filtered = filter_accounts(pattern, accounts);
+        }
+        _ => unreachable!(),
+    };
 
-fn load_creds(account: &AccountConfig) {
-	todo!();
+    Ok(())
 }
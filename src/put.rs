@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aws_sdk_cloudwatch::model::{Dimension, MetricDatum, StandardUnit};
+use aws_sdk_cloudwatch::{Client, DateTime as AwsDateTime};
+use serde::Deserialize;
+
+use crate::error::AppError;
+
+/// Maximum number of `MetricDatum` entries CloudWatch accepts per
+/// `PutMetricData` call.
+const MAX_DATUMS_PER_REQUEST: usize = 20;
+
+/// One metric emission, either a single counter/gauge `value` or a
+/// distribution expressed as parallel `values`/`counts` vectors. Mirrors
+/// what CLI flags or an `--input-file` JSON array can describe.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct MetricInput {
+    pub name: String,
+    #[serde(default)]
+    pub value: Option<f64>,
+    #[serde(default)]
+    pub values: Vec<f64>,
+    #[serde(default)]
+    pub counts: Vec<f64>,
+    #[serde(default)]
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub dimensions: HashMap<String, String>,
+    /// Unix timestamp (seconds). Defaults to now when omitted.
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+}
+
+pub fn load_metric_inputs_from_file(path: &str) -> Result<Vec<MetricInput>, AppError> {
+    let contents = std::fs::read_to_string(path)?;
+    serde_json::from_str(&contents)
+        .map_err(|e| AppError::Config(format!("unable to parse {} as JSON: {}", path, e)))
+}
+
+/// Rounds `timestamp` down to the nearest `period` boundary so repeated
+/// emissions within the same window land in the same aggregation bucket.
+pub fn round_to_period(timestamp: i64, period: i64) -> i64 {
+    if period <= 0 {
+        return timestamp;
+    }
+    timestamp - timestamp.rem_euclid(period)
+}
+
+fn parse_unit(unit: &str) -> StandardUnit {
+    StandardUnit::from(unit)
+}
+
+fn to_metric_datum(input: &MetricInput, period: i64) -> MetricDatum {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let timestamp = round_to_period(input.timestamp.unwrap_or(now), period);
+
+    let dimensions: Vec<Dimension> = input
+        .dimensions
+        .iter()
+        .map(|(name, value)| Dimension::builder().name(name).value(value).build())
+        .collect();
+
+    let mut builder = MetricDatum::builder()
+        .metric_name(&input.name)
+        .timestamp(AwsDateTime::from_secs(timestamp))
+        .set_dimensions(Some(dimensions));
+
+    if let Some(unit) = &input.unit {
+        builder = builder.unit(parse_unit(unit));
+    }
+
+    // CloudWatch rejects a datum that carries both `Values` and
+    // `StatisticValues`, so a distribution input is sent as Values+Counts
+    // rather than a pre-aggregated StatisticSet.
+    if !input.values.is_empty() {
+        builder = builder
+            .set_values(Some(input.values.clone()))
+            .set_counts(Some(if input.counts.is_empty() {
+                vec![1.0; input.values.len()]
+            } else {
+                input.counts.clone()
+            }));
+    } else {
+        builder = builder.value(input.value.unwrap_or_default());
+    }
+
+    builder.build()
+}
+
+/// Publishes `inputs` to CloudWatch, splitting them into batches of at most
+/// [`MAX_DATUMS_PER_REQUEST`] per `PutMetricData` call.
+pub async fn put_metrics(
+    client: &Client,
+    namespace: &str,
+    inputs: &[MetricInput],
+    period: i64,
+) -> Result<(), AppError> {
+    let data: Vec<MetricDatum> = inputs.iter().map(|i| to_metric_datum(i, period)).collect();
+
+    for chunk in data.chunks(MAX_DATUMS_PER_REQUEST) {
+        client
+            .put_metric_data()
+            .namespace(namespace)
+            .set_metric_data(Some(chunk.to_vec()))
+            .send()
+            .await
+            .map_err(aws_sdk_cloudwatch::Error::from)?;
+    }
+
+    Ok(())
+}
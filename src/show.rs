@@ -0,0 +1,185 @@
+use std::io::{stdout, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aws_sdk_cloudwatch::model::{Metric, MetricDataQuery, MetricStat};
+use aws_sdk_cloudwatch::{Client, DateTime as AwsDateTime};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{cursor::MoveTo, execute};
+use prettytable::{row, Table};
+
+use crate::error::AppError;
+
+/// Maximum number of `MetricDataQuery` entries `GetMetricData` accepts per
+/// call.
+const MAX_QUERIES_PER_REQUEST: usize = 100;
+
+async fn list_metrics(client: &Client, pattern: Option<&str>) -> Result<Vec<Metric>, AppError> {
+    let mut metrics = Vec::new();
+    let mut next_token = None;
+
+    loop {
+        let resp = client
+            .list_metrics()
+            .set_next_token(next_token.clone())
+            .send()
+            .await
+            .map_err(aws_sdk_cloudwatch::Error::from)?;
+
+        metrics.extend(resp.metrics().unwrap_or_default().iter().cloned());
+        next_token = resp.next_token().map(String::from);
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    if let Some(pat) = pattern {
+        metrics.retain(|m| {
+            m.metric_name().unwrap_or_default().contains(pat)
+                || m.namespace().unwrap_or_default().contains(pat)
+        });
+    }
+
+    Ok(metrics)
+}
+
+/// Fetches the most recent datapoint for each metric in `metrics`, keyed by
+/// its position in the input slice.
+async fn latest_values(client: &Client, metrics: &[Metric]) -> Result<Vec<Option<f64>>, AppError> {
+    let mut values = vec![None; metrics.len()];
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+    let start = now - 300;
+
+    for (chunk_start, chunk) in metrics.chunks(MAX_QUERIES_PER_REQUEST).enumerate() {
+        let queries: Vec<MetricDataQuery> = chunk
+            .iter()
+            .enumerate()
+            .map(|(i, metric)| {
+                let stat = MetricStat::builder()
+                    .metric(metric.clone())
+                    .period(60)
+                    .stat("Average")
+                    .build();
+                MetricDataQuery::builder()
+                    .id(format!("m{}", i))
+                    .metric_stat(stat)
+                    .return_data(true)
+                    .build()
+            })
+            .collect();
+
+        let resp = client
+            .get_metric_data()
+            .set_metric_data_queries(Some(queries))
+            .start_time(AwsDateTime::from_secs(start))
+            .end_time(AwsDateTime::from_secs(now))
+            .send()
+            .await
+            .map_err(aws_sdk_cloudwatch::Error::from)?;
+
+        for result in resp.metric_data_results().unwrap_or_default() {
+            let idx: usize = result
+                .id()
+                .and_then(|id| id.strip_prefix('m'))
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+            if let Some(value) = result.values().unwrap_or_default().first() {
+                values[chunk_start * MAX_QUERIES_PER_REQUEST + idx] = Some(*value);
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+fn dimensions_string(metric: &Metric) -> String {
+    metric
+        .dimensions()
+        .unwrap_or_default()
+        .iter()
+        .map(|d| {
+            format!(
+                "{}={}",
+                d.name().unwrap_or_default(),
+                d.value().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Renders `metrics` (optionally alongside their latest `GetMetricData`
+/// value) as an aligned table.
+async fn render_table(
+    client: &Client,
+    metrics: &[Metric],
+    with_values: bool,
+) -> Result<Table, AppError> {
+    let mut table = Table::new();
+    if with_values {
+        table.add_row(row!["Namespace", "Name", "Dimensions", "Latest"]);
+    } else {
+        table.add_row(row!["Namespace", "Name", "Dimensions"]);
+    }
+
+    let values = if with_values {
+        latest_values(client, metrics).await?
+    } else {
+        vec![None; metrics.len()]
+    };
+
+    for (metric, value) in metrics.iter().zip(values.iter()) {
+        let dims = dimensions_string(metric);
+        if with_values {
+            let value_str = value.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".into());
+            table.add_row(row![
+                metric.namespace().unwrap_or_default(),
+                metric.metric_name().unwrap_or_default(),
+                dims,
+                value_str
+            ]);
+        } else {
+            table.add_row(row![
+                metric.namespace().unwrap_or_default(),
+                metric.metric_name().unwrap_or_default(),
+                dims
+            ]);
+        }
+    }
+
+    Ok(table)
+}
+
+/// Prints the current metrics as a table once.
+pub async fn show_metrics(
+    client: &Client,
+    pattern: Option<&str>,
+    with_values: bool,
+) -> Result<(), AppError> {
+    let metrics = list_metrics(client, pattern).await?;
+    let table = render_table(client, &metrics, with_values).await?;
+    println!("Found {} metrics.", metrics.len());
+    table.printstd();
+    Ok(())
+}
+
+/// Re-renders the metrics table every `interval`, clearing the terminal
+/// between ticks, until the process is interrupted.
+pub async fn watch_metrics(
+    client: &Client,
+    pattern: Option<&str>,
+    interval: Duration,
+    with_values: bool,
+) -> Result<(), AppError> {
+    loop {
+        let metrics = list_metrics(client, pattern).await?;
+        let table = render_table(client, &metrics, with_values).await?;
+
+        let mut out = stdout();
+        execute!(out, Clear(ClearType::All), MoveTo(0, 0)).ok();
+        println!("Found {} metrics. (refreshing every {:?})", metrics.len(), interval);
+        table.printstd();
+        out.flush().ok();
+
+        tokio::time::sleep(interval).await;
+    }
+}
@@ -0,0 +1,28 @@
+use std::time::{Duration, SystemTime};
+
+use crate::error::AppError;
+
+/// Parses the repo's relative time shorthand (e.g. `"4320H"` for "4320
+/// hours ago", `"0H"` for "now"). The trailing letter selects the unit:
+/// `S` seconds, `M` minutes, `H` hours, `D` days.
+pub fn parse_relative_time(input: &str) -> Result<SystemTime, AppError> {
+    if input.len() < 2 {
+        return Err(AppError::Config(format!("invalid relative time: {}", input)));
+    }
+    let (digits, unit) = input.split_at(input.len() - 1);
+    let amount: u64 = digits
+        .parse()
+        .map_err(|_| AppError::Config(format!("invalid relative time: {}", input)))?;
+
+    let duration = match unit {
+        "S" | "s" => Duration::from_secs(amount),
+        "M" | "m" => Duration::from_secs(amount * 60),
+        "H" | "h" => Duration::from_secs(amount * 3600),
+        "D" | "d" => Duration::from_secs(amount * 86400),
+        _ => return Err(AppError::Config(format!("unknown time unit in: {}", input))),
+    };
+
+    SystemTime::now()
+        .checked_sub(duration)
+        .ok_or_else(|| AppError::Config(format!("relative time underflowed: {}", input)))
+}